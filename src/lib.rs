@@ -111,10 +111,9 @@ fn disassemble(assembled_bytes: Vec<u8>) -> Result<String, &'static str> {
         return Err("Error parsing rom: uneven number of bytes");
     }
 
-    let disassembled_string = assembled_bytes
-        // group file bytes into pairs to parse 16-bit instructions
+    // group file bytes into pairs to parse 16-bit instructions
+    let words: Vec<u16> = assembled_bytes
         .chunks(2)
-        // convert iterator of u8 pairs to iterator of u16s
         .map(|chunk| {
             if let [b1, b2] = chunk {
                 ((*b1 as u16) << 8) | (*b2 as u16)
@@ -124,49 +123,86 @@ fn disassemble(assembled_bytes: Vec<u8>) -> Result<String, &'static str> {
                 )
             }
         })
-        // convert instruction code to asm string
-        .map(convert_instruction)
-        // convert to one long string to write to output file
-        .fold(String::new(), |mut acc, inst| {
-            acc.push_str(&inst);
-            acc.push('\n');
-            acc
-        });
+        .collect();
+
+    // walk the words one instruction at a time rather than mapping 1:1, since XO-CHIP's F000
+    // long-address form consumes an extra word for its argument
+    let mut disassembled_string = String::new();
+    let mut i = 0;
+    while i < words.len() {
+        let next_word = words.get(i + 1).copied();
+        let (inst_str, consumed) = convert_instruction(words[i], next_word);
+        disassembled_string.push_str(&inst_str);
+        disassembled_string.push('\n');
+        i += consumed;
+    }
 
     Ok(disassembled_string)
 }
 
-/// Given a u16 representing an assembled chip8 instruction, return the human-readable string
-/// format of that instruction
-/// Instructions and format outlined at http://devernay.free.fr/hacks/chip8/C8TECH10.HTM
-fn convert_instruction(inst: u16) -> String {
+/// Given a u16 representing an assembled chip8 instruction and the word immediately following it
+/// in the rom (needed for the XO-CHIP `F000 NNNN` long-address form), return the human-readable
+/// string format of that instruction along with the number of 16-bit words it consumed (1, or 2
+/// for `F000 NNNN`).
+/// Original instructions and format outlined at http://devernay.free.fr/hacks/chip8/C8TECH10.HTM
+/// SUPER-CHIP instructions outlined at http://devernay.free.fr/hacks/chip8/schip.txt
+/// XO-CHIP instructions outlined at https://github.com/JohnEarnest/Octo/blob/gh-pages/docs/XO-ChipSpecification.md
+fn convert_instruction(inst: u16, next_word: Option<u16>) -> (String, usize) {
     // instructions with 16-bit opcodes and no arguments
     if inst == 0x00E0 {
-        return String::from("CLS");
+        return (String::from("CLS"), 1);
     }
     if inst == 0x00EE {
-        return String::from("RET");
+        return (String::from("RET"), 1);
+    }
+    // SUPER-CHIP: scroll/resolution/exit instructions
+    if inst == 0x00FB {
+        return (String::from("SCR"), 1);
+    }
+    if inst == 0x00FC {
+        return (String::from("SCL"), 1);
+    }
+    if inst == 0x00FD {
+        return (String::from("EXIT"), 1);
+    }
+    if inst == 0x00FE {
+        return (String::from("LOW"), 1);
+    }
+    if inst == 0x00FF {
+        return (String::from("HIGH"), 1);
+    }
+    // XO-CHIP: assign a 16-bit address to I from the following word
+    if inst == 0xF000 {
+        return match next_word {
+            Some(addr) => (String::from("LD I, 0x") + &format!("{:0>4X}", addr), 2),
+            // malformed rom: no following word to read the address from, treat as data
+            None => (String::from("0x") + &format!("{:0>4X}", inst), 1),
+        };
     }
 
     // instructions with opcode for first 4 bits, single argument for bottom 12
     let upper_four = inst >> 12;
     let lower_twelve = inst & 0x0FFF;
     let addr = lower_twelve;
+    // SUPER-CHIP: 00Cn, scroll display n lines down
+    if upper_four == 0 && (inst & 0xFFF0) == 0x00C0 {
+        return (String::from("SCD 0x") + &format!("{:X}", inst & 0x000F), 1);
+    }
     if upper_four == 0 {
-        return String::from("SYS 0x") + &format!("{:0>3X}", addr);
+        return (String::from("SYS 0x") + &format!("{:0>3X}", addr), 1);
     } // note that this interprets null bytes as SYS 0x000. Realistically this instruction is
       // probably unused
     if upper_four == 1 {
-        return String::from("JP 0x") + &format!("{:0>3X}", addr);
+        return (String::from("JP 0x") + &format!("{:0>3X}", addr), 1);
     }
     if upper_four == 2 {
-        return String::from("CALL 0x") + &format!("{:0>3X}", addr);
+        return (String::from("CALL 0x") + &format!("{:0>3X}", addr), 1);
     }
     if upper_four == 0xA {
-        return String::from("LD I, 0x") + &format!("{:0>3X}", addr);
+        return (String::from("LD I, 0x") + &format!("{:0>3X}", addr), 1);
     }
     if upper_four == 0xB {
-        return String::from("JP V0, 0x") + &format!("{:0>3X}", addr);
+        return (String::from("JP V0, 0x") + &format!("{:0>3X}", addr), 1);
     }
 
     // instructions with opcode for first 4 bits, one 4-bit arg, and one 8-bit arg
@@ -174,114 +210,242 @@ fn convert_instruction(inst: u16) -> String {
     let lower_eight = inst & 0x00FF;
     let byte = lower_eight;
     if upper_four == 3 {
-        return String::from("SE V") + &format!("{:X}", x_arg) + ", 0x" + &format!("{:0>2X}", byte);
+        return (
+            String::from("SE V") + &format!("{:X}", x_arg) + ", 0x" + &format!("{:0>2X}", byte),
+            1,
+        );
     }
     if upper_four == 4 {
-        return String::from("SNE V")
-            + &format!("{:X}", x_arg)
-            + ", 0x"
-            + &format!("{:0>2X}", byte);
+        return (
+            String::from("SNE V") + &format!("{:X}", x_arg) + ", 0x" + &format!("{:0>2X}", byte),
+            1,
+        );
     }
     if upper_four == 6 {
-        return String::from("LD V") + &format!("{:X}", x_arg) + ", 0x" + &format!("{:0>2X}", byte);
+        return (
+            String::from("LD V") + &format!("{:X}", x_arg) + ", 0x" + &format!("{:0>2X}", byte),
+            1,
+        );
     }
     if upper_four == 7 {
-        return String::from("ADD V")
-            + &format!("{:X}", x_arg)
-            + ", 0x"
-            + &format!("{:0>2X}", byte);
+        return (
+            String::from("ADD V") + &format!("{:X}", x_arg) + ", 0x" + &format!("{:0>2X}", byte),
+            1,
+        );
     }
     if upper_four == 0xC {
-        return String::from("RND V")
-            + &format!("{:X}", x_arg)
-            + ", 0x"
-            + &format!("{:0>2X}", byte);
+        return (
+            String::from("RND V") + &format!("{:X}", x_arg) + ", 0x" + &format!("{:0>2X}", byte),
+            1,
+        );
     }
 
     // instructions with opcode for first 4 and last 4 bits, two 4-bit args
     let y_arg = (inst & 0x00F0) >> 4;
     let lower_four = inst & 0x000F;
     if upper_four == 5 && lower_four == 0 {
-        return String::from("SE V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("SE V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
+    }
+    // XO-CHIP: 5xy2/5xy3, save/load an inclusive range of registers Vx..Vy through I
+    if upper_four == 5 && lower_four == 2 {
+        return (
+            String::from("LD [I], V") + &format!("{:X}", x_arg) + "..V" + &format!("{:X}", y_arg),
+            1,
+        );
+    }
+    if upper_four == 5 && lower_four == 3 {
+        return (
+            String::from("LD V")
+                + &format!("{:X}", x_arg)
+                + "..V"
+                + &format!("{:X}", y_arg)
+                + ", [I]",
+            1,
+        );
     }
     if upper_four == 8 && lower_four == 0 {
-        return String::from("LD V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("LD V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     if upper_four == 8 && lower_four == 1 {
-        return String::from("OR V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("OR V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     if upper_four == 8 && lower_four == 2 {
-        return String::from("AND V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("AND V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     if upper_four == 8 && lower_four == 3 {
-        return String::from("XOR V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("XOR V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     if upper_four == 8 && lower_four == 4 {
-        return String::from("ADD V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("ADD V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     if upper_four == 8 && lower_four == 5 {
-        return String::from("SUB V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("SUB V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     if upper_four == 8 && lower_four == 7 {
-        return String::from("SUBN V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("SUBN V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     if upper_four == 9 && lower_four == 0 {
-        return String::from("SNE V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg);
+        return (
+            String::from("SNE V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
     }
     // the second 4-bit arg is ignored for these two
     if upper_four == 8 && lower_four == 6 {
-        return String::from("SHR V") + &format!("{:X}", x_arg);
+        return (String::from("SHR V") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 8 && lower_four == 0xE {
-        return String::from("SHL V") + &format!("{:X}", x_arg);
+        return (String::from("SHL V") + &format!("{:X}", x_arg), 1);
     }
 
     // instructions with opcde for first 4 bits, three 4-bit args
     let nibble = lower_four;
+    // SUPER-CHIP: Dxy0, draw a 16x16 sprite in high-res mode instead of the usual Nx8 sprite
+    if upper_four == 0xD && nibble == 0 {
+        return (
+            String::from("DRW V") + &format!("{:X}", x_arg) + ", V" + &format!("{:X}", y_arg),
+            1,
+        );
+    }
     if upper_four == 0xD {
-        return String::from("DRW V")
-            + &format!("{:X}", x_arg)
-            + ", V"
-            + &format!("{:X}", y_arg)
-            + ", 0x"
-            + &format!("{:X}", nibble);
+        return (
+            String::from("DRW V")
+                + &format!("{:X}", x_arg)
+                + ", V"
+                + &format!("{:X}", y_arg)
+                + ", 0x"
+                + &format!("{:X}", nibble),
+            1,
+        );
     }
 
     // instructions with opcode for first 4 bits and last 8 bits, one 4-bit arg
     if upper_four == 0xE && lower_eight == 0x9E {
-        return String::from("SKP V") + &format!("{:X}", x_arg);
+        return (String::from("SKP V") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 0xE && lower_eight == 0xA1 {
-        return String::from("SKNP V") + &format!("{:X}", x_arg);
+        return (String::from("SKNP V") + &format!("{:X}", x_arg), 1);
+    }
+    if upper_four == 0xF && lower_eight == 0x01 {
+        // XO-CHIP: Fx01, select the drawing plane(s) given by the bitmask x
+        return (String::from("PLANE 0x") + &format!("{:X}", x_arg), 1);
+    }
+    if inst == 0xF002 {
+        // XO-CHIP: F002, load 16 bytes starting at I into the audio pattern buffer
+        return (String::from("LD AUDIO, [I]"), 1);
     }
     if upper_four == 0xF && lower_eight == 0x07 {
-        return String::from("LD V") + &format!("{:X}", x_arg) + ", DT";
+        return (String::from("LD V") + &format!("{:X}", x_arg) + ", DT", 1);
     }
     if upper_four == 0xF && lower_eight == 0x0A {
-        return String::from("LD V") + &format!("{:X}", x_arg) + ", K";
+        return (String::from("LD V") + &format!("{:X}", x_arg) + ", K", 1);
     }
     if upper_four == 0xF && lower_eight == 0x15 {
-        return String::from("LD DT, v") + &format!("{:X}", x_arg);
+        return (String::from("LD DT, v") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 0xF && lower_eight == 0x18 {
-        return String::from("LD ST, V") + &format!("{:X}", x_arg);
+        return (String::from("LD ST, V") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 0xF && lower_eight == 0x1E {
-        return String::from("ADD I, V") + &format!("{:X}", x_arg);
+        return (String::from("ADD I, V") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 0xF && lower_eight == 0x29 {
-        return String::from("LD F, V") + &format!("{:X}", x_arg);
+        return (String::from("LD F, V") + &format!("{:X}", x_arg), 1);
+    }
+    if upper_four == 0xF && lower_eight == 0x30 {
+        // SUPER-CHIP: Fx30, point F at the high-res (8x10) font sprite for Vx
+        return (String::from("LD HF, V") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 0xF && lower_eight == 0x33 {
-        return String::from("LD B, V") + &format!("{:X}", x_arg);
+        return (String::from("LD B, V") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 0xF && lower_eight == 0x55 {
-        return String::from("LD [I], V") + &format!("{:X}", x_arg);
+        return (String::from("LD [I], V") + &format!("{:X}", x_arg), 1);
     }
     if upper_four == 0xF && lower_eight == 0x65 {
-        return String::from("LD V") + &format!("{:X}", x_arg) + ", [I]";
+        return (String::from("LD V") + &format!("{:X}", x_arg) + ", [I]", 1);
+    }
+    if upper_four == 0xF && lower_eight == 0x75 {
+        // SUPER-CHIP: Fx75, store V0..Vx into the HP-48 RPL user flags
+        return (String::from("LD R, V") + &format!("{:X}", x_arg), 1);
+    }
+    if upper_four == 0xF && lower_eight == 0x85 {
+        // SUPER-CHIP: Fx85, load V0..Vx from the HP-48 RPL user flags
+        return (String::from("LD V") + &format!("{:X}", x_arg) + ", R", 1);
     }
 
     // instruction not found, probably a bitmap graphic or other data
-    String::from("0x") + &format!("{:0>4X}", inst)
+    (String::from("0x") + &format!("{:0>4X}", inst), 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_f000_long_address_and_advances_by_two_words() {
+        let (inst_str, consumed) = convert_instruction(0xF000, Some(0x1234));
+        assert_eq!(inst_str, "LD I, 0x1234");
+        assert_eq!(consumed, 2);
+    }
+
+    #[test]
+    fn f000_with_no_trailing_word_falls_back_to_raw_data() {
+        let (inst_str, consumed) = convert_instruction(0xF000, None);
+        assert_eq!(inst_str, "0xF000");
+        assert_eq!(consumed, 1);
+    }
+
+    #[test]
+    fn decodes_00cn_scroll_down() {
+        let (inst_str, consumed) = convert_instruction(0x00C5, None);
+        assert_eq!(inst_str, "SCD 0x5");
+        assert_eq!(consumed, 1);
+    }
+
+    #[test]
+    fn decodes_dxy0_as_16x16_sprite_and_dxyn_as_normal_sprite() {
+        let (sprite_16x16, consumed) = convert_instruction(0xD120, None);
+        assert_eq!(sprite_16x16, "DRW V1, V2");
+        assert_eq!(consumed, 1);
+
+        let (sprite_nx8, consumed) = convert_instruction(0xD123, None);
+        assert_eq!(sprite_nx8, "DRW V1, V2, 0x3");
+        assert_eq!(consumed, 1);
+    }
+
+    #[test]
+    fn decodes_5xy2_and_5xy3_register_range_save_load() {
+        let (save, consumed) = convert_instruction(0x5232, None);
+        assert_eq!(save, "LD [I], V2..V3");
+        assert_eq!(consumed, 1);
+
+        let (load, consumed) = convert_instruction(0x5233, None);
+        assert_eq!(load, "LD V2..V3, [I]");
+        assert_eq!(consumed, 1);
+    }
 }